@@ -0,0 +1,148 @@
+use std::fmt::Formatter;
+
+use crate::IcuDateTimeStyle;
+
+/// The width a CLDR date/time field was written with in a skeleton, e.g. `y` vs `yy` vs `yyyy`.
+/// Numeric fields track their literal digit count; text fields collapse the wider range of
+/// CLDR letter-repetition rules down to the handful of display lengths translators actually
+/// choose between.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldWidth {
+    Numeric(u8),
+    Short,
+    Long,
+    Narrow,
+}
+
+/// Whether an `h`/`H`/`K`/`k` skeleton letter requested a 12-hour or 24-hour clock, and whether
+/// hour `0` is represented as `0` or rolled over to `12`/`24`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HourCycle {
+    /// `h`: 12-hour clock, midnight is `12`.
+    H12,
+    /// `K`: 12-hour clock, midnight is `0`.
+    H11,
+    /// `H`: 24-hour clock, midnight is `0`.
+    H23,
+    /// `k`: 24-hour clock, midnight is `24`.
+    H24,
+}
+
+/// A single typed field parsed out of a CLDR date/time skeleton such as `yMMMd` or `jmsz`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DateTimeComponent {
+    Year(FieldWidth),
+    Month(FieldWidth),
+    Day(FieldWidth),
+    Weekday(FieldWidth),
+    Hour(FieldWidth, HourCycle),
+    Minute(FieldWidth),
+    Second(FieldWidth),
+    TimeZone(FieldWidth),
+}
+
+/// A skeleton letter that isn't part of the CLDR date/time field set this crate understands.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownSkeletonField(pub char);
+
+impl std::fmt::Display for UnknownSkeletonField {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown date/time skeleton field '{}'", self.0)
+    }
+}
+
+impl std::error::Error for UnknownSkeletonField {}
+
+/// Parses a CLDR date/time skeleton (e.g. `yMMMd`, `jmsz`) into its typed components by scanning
+/// left to right and grouping consecutive runs of the same field letter into a single component
+/// whose width is the run length. Letters outside the supported field set are rejected rather
+/// than silently ignored, so callers can surface a diagnostic instead of guessing.
+pub fn parse_skeleton(skeleton: &str) -> Result<Vec<DateTimeComponent>, UnknownSkeletonField> {
+    let chars: Vec<char> = skeleton.chars().collect();
+    let mut components = Vec::new();
+    let mut index = 0;
+    while index < chars.len() {
+        let field = chars[index];
+        let mut width: u8 = 0;
+        while index < chars.len() && chars[index] == field {
+            width += 1;
+            index += 1;
+        }
+        components.push(component_for(field, width)?);
+    }
+    Ok(components)
+}
+
+fn text_width(count: u8) -> FieldWidth {
+    match count {
+        1 | 2 => FieldWidth::Numeric(count),
+        3 => FieldWidth::Short,
+        4 => FieldWidth::Long,
+        _ => FieldWidth::Narrow,
+    }
+}
+
+fn component_for(field: char, width: u8) -> Result<DateTimeComponent, UnknownSkeletonField> {
+    Ok(match field {
+        'y' | 'Y' => DateTimeComponent::Year(FieldWidth::Numeric(width)),
+        'M' | 'L' => DateTimeComponent::Month(text_width(width)),
+        'd' => DateTimeComponent::Day(FieldWidth::Numeric(width)),
+        'E' | 'e' | 'c' => DateTimeComponent::Weekday(text_width(width)),
+        // `j` is the CLDR "locale preferred hour" letter; treat it like `h` since the caller
+        // doesn't have a locale to resolve the actual preference against.
+        'h' | 'j' => DateTimeComponent::Hour(FieldWidth::Numeric(width), HourCycle::H12),
+        'K' => DateTimeComponent::Hour(FieldWidth::Numeric(width), HourCycle::H11),
+        'H' => DateTimeComponent::Hour(FieldWidth::Numeric(width), HourCycle::H23),
+        'k' => DateTimeComponent::Hour(FieldWidth::Numeric(width), HourCycle::H24),
+        'm' => DateTimeComponent::Minute(FieldWidth::Numeric(width)),
+        's' => DateTimeComponent::Second(FieldWidth::Numeric(width)),
+        'z' | 'Z' | 'v' | 'V' | 'O' | 'x' | 'X' => {
+            DateTimeComponent::TimeZone(FieldWidth::Numeric(width))
+        }
+        other => return Err(UnknownSkeletonField(other)),
+    })
+}
+
+impl IcuDateTimeStyle {
+    /// Parses this style's raw CLDR skeleton token (its [`token()`](IcuDateTimeStyle::token)) into
+    /// typed components, surfacing [`UnknownSkeletonField`] if the token contains a letter outside
+    /// the supported CLDR field set rather than guessing at its meaning.
+    pub fn components(&self) -> Result<Vec<DateTimeComponent>, UnknownSkeletonField> {
+        parse_skeleton(self.token())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_run_lengths_into_field_widths() {
+        assert_eq!(
+            parse_skeleton("yMMMd").unwrap(),
+            vec![
+                DateTimeComponent::Year(FieldWidth::Numeric(1)),
+                DateTimeComponent::Month(FieldWidth::Short),
+                DateTimeComponent::Day(FieldWidth::Numeric(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_hour_cycle_letters() {
+        assert_eq!(
+            parse_skeleton("jmsz").unwrap(),
+            vec![
+                DateTimeComponent::Hour(FieldWidth::Numeric(1), HourCycle::H12),
+                DateTimeComponent::Minute(FieldWidth::Numeric(1)),
+                DateTimeComponent::Second(FieldWidth::Numeric(1)),
+                DateTimeComponent::TimeZone(FieldWidth::Numeric(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_skeleton_letters() {
+        assert_eq!(parse_skeleton("yq").unwrap_err(), UnknownSkeletonField('q'));
+    }
+}
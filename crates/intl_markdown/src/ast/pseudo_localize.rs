@@ -0,0 +1,261 @@
+use crate::{BlockNode, Document, InlineContent};
+
+use super::visitor::Traverse;
+use super::visitor_mut::{visit_document_mut, VisitorMut};
+
+/// Rewrites a `Document` in place into a pseudo-localized variant for layout/i18n QA: ASCII
+/// letters in visible `Text` content are swapped for accented look-alikes, each text run is
+/// padded to simulate the word-length expansion translations typically cause, and bracket
+/// markers are added so truncation is obvious at a glance: `[` at the start of the first visible
+/// text run and `]` at the end of the last one. A placeholder or code span before the first text
+/// run or after the last one stays outside the brackets, and a message with no visible text gets
+/// no brackets. ICU placeholders,
+/// variable names, plural/select keys, link destinations, and code spans are left untouched so
+/// the pseudo-localized message still parses and formats identically to the original.
+pub fn pseudo_localize(document: &mut Document) {
+    let mut transform = PseudoLocalizeText;
+    visit_document_mut(&mut transform, document);
+    if let Some(first) = first_text_mut(document.blocks_mut()) {
+        first.insert(0, '[');
+    }
+    if let Some(last) = last_text_mut(document.blocks_mut()) {
+        last.push(']');
+    }
+}
+
+struct PseudoLocalizeText;
+
+impl VisitorMut for PseudoLocalizeText {
+    fn visit_text(&mut self, node: &mut String) -> Traverse {
+        *node = pseudo_localize_text(node);
+        Traverse::Continue
+    }
+}
+
+/// The fraction of extra length appended to each text run, matching the rule-of-thumb that
+/// translated UI strings run ~30-35% longer than their English source.
+const EXPANSION_FACTOR: f64 = 0.35;
+
+fn pseudo_localize_text(text: &str) -> String {
+    let transliterated: String = text.chars().map(transliterate).collect();
+    let padding_len = ((transliterated.chars().count() as f64) * EXPANSION_FACTOR).round() as usize;
+    if padding_len == 0 {
+        return transliterated;
+    }
+    let mut padded = transliterated;
+    padded.push(' ');
+    padded.extend(std::iter::repeat('~').take(padding_len));
+    padded
+}
+
+/// Maps an ASCII letter to an accented look-alike that reads the same but makes hard-coded,
+/// un-translated strings visually obvious next to genuinely pseudo-localized ones. Anything
+/// that isn't an ASCII letter (digits, punctuation, whitespace) passes through unchanged.
+fn transliterate(ch: char) -> char {
+    match ch {
+        'a' => 'à',
+        'b' => 'ƀ',
+        'c' => 'ç',
+        'd' => 'ð',
+        'e' => 'é',
+        'f' => 'ƒ',
+        'g' => 'ğ',
+        'h' => 'ħ',
+        'i' => 'î',
+        'j' => 'ĵ',
+        'k' => 'ķ',
+        'l' => 'ŀ',
+        'm' => 'ṁ',
+        'n' => 'ñ',
+        'o' => 'ö',
+        'p' => 'ṗ',
+        'q' => 'ɋ',
+        'r' => 'ř',
+        's' => 'š',
+        't' => 'ţ',
+        'u' => 'ü',
+        'v' => 'ṽ',
+        'w' => 'ŵ',
+        'x' => 'ẍ',
+        'y' => 'ý',
+        'z' => 'ž',
+        'A' => 'Å',
+        'B' => 'Ɓ',
+        'C' => 'Ç',
+        'D' => 'Ð',
+        'E' => 'É',
+        'F' => 'Ƒ',
+        'G' => 'Ğ',
+        'H' => 'Ħ',
+        'I' => 'Î',
+        'J' => 'Ĵ',
+        'K' => 'Ķ',
+        'L' => 'Ŀ',
+        'M' => 'Ṁ',
+        'N' => 'Ñ',
+        'O' => 'Ö',
+        'P' => 'Ṗ',
+        'Q' => 'Ɋ',
+        'R' => 'Ř',
+        'S' => 'Š',
+        'T' => 'Ţ',
+        'U' => 'Ü',
+        'V' => 'Ṽ',
+        'W' => 'Ŵ',
+        'X' => 'Ẍ',
+        'Y' => 'Ý',
+        'Z' => 'Ž',
+        other => other,
+    }
+}
+
+fn first_text_mut(blocks: &mut Vec<BlockNode>) -> Option<&mut String> {
+    edge_text_mut(blocks, Edge::First)
+}
+
+fn last_text_mut(blocks: &mut Vec<BlockNode>) -> Option<&mut String> {
+    edge_text_mut(blocks, Edge::Last)
+}
+
+/// Which end of the document to search from; shared by [`edge_text_mut`] and
+/// [`edge_text_in_inline_mut`] so the first/last lookups can be one direction-parameterized walk
+/// instead of two near-identical copies.
+#[derive(Clone, Copy)]
+enum Edge {
+    First,
+    Last,
+}
+
+impl Edge {
+    fn iter<'a, T>(self, items: &'a mut [T]) -> Box<dyn Iterator<Item = &'a mut T> + 'a> {
+        match self {
+            Edge::First => Box::new(items.iter_mut()),
+            Edge::Last => Box::new(items.iter_mut().rev()),
+        }
+    }
+}
+
+fn edge_text_mut(blocks: &mut Vec<BlockNode>, edge: Edge) -> Option<&mut String> {
+    for block in edge.iter(blocks) {
+        match block {
+            BlockNode::Paragraph(paragraph) => {
+                if let Some(text) = edge_text_in_inline_mut(paragraph.content_mut(), edge) {
+                    return Some(text);
+                }
+            }
+            BlockNode::Heading(heading) => {
+                if let Some(text) = edge_text_in_inline_mut(heading.content_mut(), edge) {
+                    return Some(text);
+                }
+            }
+            BlockNode::InlineContent(inline_content) => {
+                if let Some(text) = edge_text_in_inline_mut(inline_content, edge) {
+                    return Some(text);
+                }
+            }
+            BlockNode::CodeBlock(_) | BlockNode::ThematicBreak => {}
+        }
+    }
+    None
+}
+
+fn edge_text_in_inline_mut(content: &mut Vec<InlineContent>, edge: Edge) -> Option<&mut String> {
+    for node in edge.iter(content) {
+        match node {
+            InlineContent::Text(text) => return Some(text),
+            InlineContent::Emphasis(emphasis) => {
+                if let Some(text) = edge_text_in_inline_mut(emphasis.content_mut(), edge) {
+                    return Some(text);
+                }
+            }
+            InlineContent::Strong(strong) => {
+                if let Some(text) = edge_text_in_inline_mut(strong.content_mut(), edge) {
+                    return Some(text);
+                }
+            }
+            InlineContent::Strikethrough(strikethrough) => {
+                if let Some(text) = edge_text_in_inline_mut(strikethrough.content_mut(), edge) {
+                    return Some(text);
+                }
+            }
+            InlineContent::Link(link) => {
+                if let Some(text) = edge_text_in_inline_mut(link.label_mut(), edge) {
+                    return Some(text);
+                }
+            }
+            InlineContent::Hook(hook) => {
+                if let Some(text) = edge_text_in_inline_mut(hook.content_mut(), edge) {
+                    return Some(text);
+                }
+            }
+            InlineContent::CodeSpan(_)
+            | InlineContent::Icu(_)
+            | InlineContent::IcuPound
+            | InlineContent::HardLineBreak => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transliterate_maps_every_ascii_letter_to_a_non_identity_character() {
+        for ch in ('a'..='z').chain('A'..='Z') {
+            assert_ne!(
+                transliterate(ch),
+                ch,
+                "letter '{ch}' passed through transliterate unmapped"
+            );
+        }
+    }
+
+    #[test]
+    fn transliterate_leaves_non_letters_untouched() {
+        for ch in ['0', ' ', '!', '{', '}', '#'] {
+            assert_eq!(transliterate(ch), ch);
+        }
+    }
+
+    #[test]
+    fn pseudo_localize_text_transliterates_and_pads_to_expand_length() {
+        let result = pseudo_localize_text("hello world");
+        assert_eq!(result.chars().next().unwrap(), 'ħ');
+        assert!(
+            result.chars().count() > "hello world".chars().count(),
+            "expected padded text to be longer than the source: {result:?}"
+        );
+        assert!(result.contains('~'), "expected padding tildes: {result:?}");
+    }
+
+    #[test]
+    fn first_and_last_text_mut_bracket_the_outermost_visible_text_runs() {
+        let mut blocks = vec![
+            BlockNode::InlineContent(vec![InlineContent::Text("start".to_string())]),
+            BlockNode::InlineContent(vec![InlineContent::Text("end".to_string())]),
+        ];
+        if let Some(first) = first_text_mut(&mut blocks) {
+            first.insert(0, '[');
+        }
+        if let Some(last) = last_text_mut(&mut blocks) {
+            last.push(']');
+        }
+
+        let BlockNode::InlineContent(first_items) = &blocks[0] else {
+            panic!("expected inline content block");
+        };
+        let BlockNode::InlineContent(last_items) = &blocks[1] else {
+            panic!("expected inline content block");
+        };
+        let InlineContent::Text(first_text) = &first_items[0] else {
+            panic!("expected text node");
+        };
+        let InlineContent::Text(last_text) = &last_items[0] else {
+            panic!("expected text node");
+        };
+        assert_eq!(first_text, "[start");
+        assert_eq!(last_text, "end]");
+    }
+}
@@ -0,0 +1,396 @@
+use crate::{
+    CodeBlock, CodeSpan, Document, Heading, Icu, IcuDate, IcuDateTimeStyle, IcuNumber,
+    IcuNumberStyle, IcuPluralArm, IcuTime, IcuVariable, Link, TextOrPlaceholder,
+};
+
+use super::visitor::{Traversal, Traverse, Visitor};
+
+/// Renders a parsed [`Document`] back to the canonical markdown+ICU source text it represents,
+/// making the AST losslessly serializable to text instead of only to the JSON `Serialize` forms.
+/// This is the formatter counterpart to parsing: running a message through
+/// `SourceWriter::format` twice should be idempotent, so definition and translation files can be
+/// rewritten to a single stable style.
+pub struct SourceWriter {
+    /// Stack of in-progress output buffers. Writes always land on the last buffer; plural/select
+    /// arms push a fresh one so their rendered text can be captured, reordered, and spliced back
+    /// in once every arm in the construct has been visited.
+    buffers: Vec<String>,
+    /// Per plural/select construct, the arms collected so far along with their sort rank, so
+    /// `exit_icu_plural`/`exit_icu_select` can emit them in canonical order regardless of the
+    /// order they appeared in the source.
+    arm_groups: Vec<Vec<(ArmRank, String)>>,
+    /// Set by `visit_icu_plural`/`visit_icu_select`/`visit_icu_date`/`visit_icu_time` and
+    /// consumed by the following `exit_icu_variable`, since the ` plural`/` date`/etc. keyword is
+    /// only known by the wrapping construct but can only be written once the variable name
+    /// itself has finished.
+    pending_keyword: Option<&'static str>,
+}
+
+/// Sort rank for a plural/select arm: explicit `=N` arms first (ordered by `N`), then keyword
+/// arms in source order, with `other` always last.
+type ArmRank = (u8, i64);
+
+impl SourceWriter {
+    pub fn new() -> Self {
+        Self {
+            buffers: vec![String::new()],
+            arm_groups: Vec::new(),
+            pending_keyword: None,
+        }
+    }
+
+    /// Formats `document` back to its canonical source text.
+    pub fn format(document: &Document) -> String {
+        let mut writer = Self::new();
+        Traversal::traverse_document(&mut writer, document);
+        writer.finish()
+    }
+
+    fn write(&mut self, text: &str) {
+        self.buffers
+            .last_mut()
+            .expect("SourceWriter always has a root buffer")
+            .push_str(text);
+    }
+
+    fn push_buffer(&mut self) {
+        self.buffers.push(String::new());
+    }
+
+    fn pop_buffer(&mut self) -> String {
+        self.buffers.pop().expect("pop_buffer without matching push")
+    }
+
+    fn finish(mut self) -> String {
+        debug_assert_eq!(self.buffers.len(), 1, "unbalanced buffer push/pop");
+        self.buffers.pop().unwrap_or_default()
+    }
+
+    fn arm_rank(selector: &str) -> ArmRank {
+        if selector == "other" {
+            (2, 0)
+        } else if let Some(exact) = selector.strip_prefix('=').and_then(|n| n.parse().ok()) {
+            (0, exact)
+        } else {
+            (1, 0)
+        }
+    }
+
+    fn write_date_time_style(&mut self, style: Option<&IcuDateTimeStyle>) {
+        if let Some(style) = style {
+            self.write(", ");
+            self.write(style.token());
+        }
+    }
+}
+
+/// Escapes markdown control characters and literal ICU braces so that re-parsing the emitted
+/// text reproduces the same `Text` content rather than being interpreted as syntax.
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        if matches!(
+            ch,
+            '\\' | '*' | '_' | '~' | '`' | '[' | ']' | '{' | '}' | '<' | '>'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+/// Picks a backtick fence at least one character longer than the longest run of backticks
+/// already present in `content`, so the code span round-trips without prematurely closing.
+fn code_span_fence(content: &str) -> String {
+    let longest_run = content
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    "`".repeat(longest_run + 1)
+}
+
+/// Picks a backtick fence for a code *block*: the same rule as [`code_span_fence`], but clamped
+/// to a minimum of three backticks, since that's the shortest fence markdown recognizes as a
+/// block rather than a span.
+fn code_block_fence(content: &str) -> String {
+    let longest_run = content
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    "`".repeat((longest_run + 1).max(3))
+}
+
+impl Visitor for SourceWriter {
+    fn visit_heading(&mut self, node: &Heading) -> Traverse {
+        self.write(&"#".repeat(node.level() as usize));
+        self.write(" ");
+        Traverse::Continue
+    }
+
+    fn exit_heading(&mut self, _node: &Heading) {
+        self.write("\n\n");
+    }
+
+    fn exit_paragraph(&mut self, _node: &crate::Paragraph) {
+        self.write("\n\n");
+    }
+
+    fn visit_thematic_break(&mut self) -> Traverse {
+        self.write("---\n\n");
+        Traverse::Continue
+    }
+
+    fn visit_hard_line_break(&mut self) -> Traverse {
+        self.write("\\\n");
+        Traverse::Continue
+    }
+
+    fn visit_code_block(&mut self, node: &CodeBlock) -> Traverse {
+        let fence = code_block_fence(node.content());
+        self.write(&fence);
+        if let Some(language) = node.language() {
+            self.write(language);
+        }
+        self.write("\n");
+        self.write(node.content());
+        self.write("\n");
+        self.write(&fence);
+        self.write("\n\n");
+        Traverse::Continue
+    }
+
+    fn visit_code_span(&mut self, node: &CodeSpan) -> Traverse {
+        let fence = code_span_fence(node.content());
+        self.write(&fence);
+        self.write(node.content());
+        self.write(&fence);
+        Traverse::Continue
+    }
+
+    fn visit_emphasis(&mut self, _node: &crate::Emphasis) -> Traverse {
+        self.write("_");
+        Traverse::Continue
+    }
+    fn exit_emphasis(&mut self, _node: &crate::Emphasis) {
+        self.write("_");
+    }
+
+    fn visit_strong(&mut self, _node: &crate::Strong) -> Traverse {
+        self.write("**");
+        Traverse::Continue
+    }
+    fn exit_strong(&mut self, _node: &crate::Strong) {
+        self.write("**");
+    }
+
+    fn visit_strikethrough(&mut self, _node: &crate::Strikethrough) -> Traverse {
+        self.write("~~");
+        Traverse::Continue
+    }
+    fn exit_strikethrough(&mut self, _node: &crate::Strikethrough) {
+        self.write("~~");
+    }
+
+    fn visit_link(&mut self, _node: &Link) -> Traverse {
+        self.write("[");
+        Traverse::Continue
+    }
+
+    fn visit_link_destination(&mut self, node: &TextOrPlaceholder) -> Traverse {
+        self.write("](");
+        if let TextOrPlaceholder::Text(text) = node {
+            self.write(&escape_text(text));
+        }
+        Traverse::Continue
+    }
+
+    fn exit_link_destination(&mut self, _node: &TextOrPlaceholder) {
+        self.write(")");
+    }
+
+    fn visit_hook(&mut self, _node: &crate::Hook) -> Traverse {
+        self.write("$[");
+        Traverse::Continue
+    }
+
+    fn exit_hook(&mut self, node: &crate::Hook) {
+        self.write("](");
+        self.write(node.name());
+        self.write(")");
+    }
+
+    fn visit_text(&mut self, node: &String) -> Traverse {
+        self.write(&escape_text(node));
+        Traverse::Continue
+    }
+
+    fn visit_icu(&mut self, _node: &Icu) -> Traverse {
+        self.write("{");
+        Traverse::Continue
+    }
+
+    fn exit_icu(&mut self, _node: &Icu) {
+        self.write("}");
+    }
+
+    fn visit_icu_variable(&mut self, node: &IcuVariable) -> Traverse {
+        self.write(node.name());
+        Traverse::Continue
+    }
+
+    fn exit_icu_variable(&mut self, _node: &IcuVariable) {
+        if let Some(keyword) = self.pending_keyword.take() {
+            self.write(", ");
+            self.write(keyword);
+        }
+    }
+
+    fn visit_icu_plural(&mut self, _node: &crate::IcuPlural) -> Traverse {
+        self.pending_keyword = Some("plural");
+        self.arm_groups.push(Vec::new());
+        Traverse::Continue
+    }
+
+    fn exit_icu_plural(&mut self, _node: &crate::IcuPlural) {
+        self.write(", ");
+        self.write(&Self::join_sorted_arms(self.arm_groups.pop().unwrap()));
+    }
+
+    fn visit_icu_select(&mut self, _node: &crate::IcuSelect) -> Traverse {
+        self.pending_keyword = Some("select");
+        self.arm_groups.push(Vec::new());
+        Traverse::Continue
+    }
+
+    fn exit_icu_select(&mut self, _node: &crate::IcuSelect) {
+        self.write(", ");
+        self.write(&Self::join_sorted_arms(self.arm_groups.pop().unwrap()));
+    }
+
+    fn visit_icu_plural_arm(&mut self, node: &IcuPluralArm) -> Traverse {
+        self.push_buffer();
+        self.write(node.selector());
+        self.write(" {");
+        Traverse::Continue
+    }
+
+    fn exit_icu_plural_arm(&mut self, node: &IcuPluralArm) {
+        self.write("}");
+        let rendered = self.pop_buffer();
+        self.arm_groups
+            .last_mut()
+            .expect("plural/select arm visited outside of its construct")
+            .push((Self::arm_rank(node.selector()), rendered));
+    }
+
+    fn visit_icu_date(&mut self, _node: &IcuDate) -> Traverse {
+        self.pending_keyword = Some("date");
+        Traverse::Continue
+    }
+
+    fn exit_icu_date(&mut self, node: &IcuDate) {
+        self.write_date_time_style(node.style.as_ref());
+    }
+
+    fn visit_icu_time(&mut self, _node: &IcuTime) -> Traverse {
+        self.pending_keyword = Some("time");
+        Traverse::Continue
+    }
+
+    fn exit_icu_time(&mut self, node: &IcuTime) {
+        self.write_date_time_style(node.style.as_ref());
+    }
+
+    fn visit_icu_number(&mut self, node: &IcuNumber) -> Traverse {
+        self.write(node.variable().name());
+        self.write(", number");
+        Traverse::Continue
+    }
+
+    fn visit_icu_number_style(&mut self, node: &IcuNumberStyle) -> Traverse {
+        self.write(", ");
+        self.write(node.token());
+        Traverse::Continue
+    }
+
+    fn visit_icu_pound(&mut self) -> Traverse {
+        self.write("#");
+        Traverse::Continue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn code_block_fence_is_at_least_three_backticks() {
+        assert_eq!(code_block_fence(""), "```");
+        assert_eq!(code_block_fence("no backticks here"), "```");
+    }
+
+    #[test]
+    fn code_block_fence_grows_past_the_longest_run_in_content() {
+        assert_eq!(code_block_fence("has a ` single backtick"), "```");
+        assert_eq!(code_block_fence("has ``` three in a row"), "````");
+        assert_eq!(code_block_fence("has ```` four in a row"), "`````");
+    }
+
+    #[test]
+    fn code_span_fence_grows_past_the_longest_run_in_content() {
+        assert_eq!(code_span_fence("plain"), "`");
+        assert_eq!(code_span_fence("has a ` backtick"), "``");
+        assert_eq!(code_span_fence("has `` two in a row"), "```");
+    }
+
+    #[test]
+    fn escape_text_escapes_literal_icu_braces() {
+        assert_eq!(escape_text("{count}"), "\\{count\\}");
+    }
+
+    #[test]
+    fn escape_text_escapes_markdown_control_characters() {
+        assert_eq!(escape_text("*bold*"), "\\*bold\\*");
+        assert_eq!(escape_text("`code`"), "\\`code\\`");
+        assert_eq!(escape_text("_em_ ~~s~~ [x] <b>"), "\\_em\\_ \\~\\~s\\~\\~ \\[x\\] \\<b\\>");
+    }
+
+    #[test]
+    fn escape_text_leaves_plain_text_untouched() {
+        assert_eq!(escape_text("plain text, no specials"), "plain text, no specials");
+    }
+
+    #[test]
+    fn arm_rank_orders_exact_arms_by_value_before_keyword_arms_before_other() {
+        assert!(SourceWriter::arm_rank("=0") < SourceWriter::arm_rank("=2"));
+        assert!(SourceWriter::arm_rank("=2") < SourceWriter::arm_rank("one"));
+        assert!(SourceWriter::arm_rank("one") < SourceWriter::arm_rank("other"));
+        assert!(SourceWriter::arm_rank("=0") < SourceWriter::arm_rank("other"));
+    }
+
+    #[test]
+    fn join_sorted_arms_reorders_to_exact_then_keyword_then_other() {
+        let arms = vec![
+            (SourceWriter::arm_rank("other"), "other {rest}".to_string()),
+            (SourceWriter::arm_rank("one"), "one {# item}".to_string()),
+            (SourceWriter::arm_rank("=0"), "=0 {none}".to_string()),
+        ];
+        assert_eq!(
+            SourceWriter::join_sorted_arms(arms),
+            "=0 {none} one {# item} other {rest}"
+        );
+    }
+}
+
+impl SourceWriter {
+    fn join_sorted_arms(mut arms: Vec<(ArmRank, String)>) -> String {
+        arms.sort_by_key(|(rank, _)| *rank);
+        arms.into_iter()
+            .map(|(_, text)| text)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
@@ -4,272 +4,614 @@ use crate::{
     IcuVariable, InlineContent, Link, Paragraph, Strikethrough, Strong, TextOrPlaceholder,
 };
 
+use super::icu_date_time_skeleton::{DateTimeComponent, UnknownSkeletonField};
+
+/// Control-flow signal returned by a `Visitor`'s `visit_*` methods, letting a pass prune or
+/// short-circuit a traversal instead of always walking every subtree.
+#[must_use]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Traverse {
+    /// Keep walking into this node's children as usual.
+    Continue,
+    /// Don't descend into this node's children, but still fire its `exit_*` callback and keep
+    /// walking the rest of the tree.
+    SkipChildren,
+    /// Unwind the entire traversal immediately. No further `visit_*` callbacks fire; the
+    /// `exit_*` callbacks of the node currently being visited and of each of its ancestors
+    /// still fire as the walk unwinds.
+    Stop,
+}
+
 pub trait Visitor {
-    fn visit_block_node(&mut self, _node: &BlockNode) {}
+    fn visit_block_node(&mut self, _node: &BlockNode) -> Traverse {
+        Traverse::Continue
+    }
     fn exit_block_node(&mut self, _node: &BlockNode) {}
-    fn visit_code_block(&mut self, _node: &CodeBlock) {}
+    fn visit_code_block(&mut self, _node: &CodeBlock) -> Traverse {
+        Traverse::Continue
+    }
     fn exit_code_block(&mut self, _node: &CodeBlock) {}
-    fn visit_code_span(&mut self, _node: &CodeSpan) {}
+    fn visit_code_span(&mut self, _node: &CodeSpan) -> Traverse {
+        Traverse::Continue
+    }
     fn exit_code_span(&mut self, _node: &CodeSpan) {}
-    fn visit_document(&mut self, _node: &Document) {}
+    fn visit_document(&mut self, _node: &Document) -> Traverse {
+        Traverse::Continue
+    }
     fn exit_document(&mut self, _node: &Document) {}
-    fn visit_emphasis(&mut self, _node: &Emphasis) {}
+    fn visit_emphasis(&mut self, _node: &Emphasis) -> Traverse {
+        Traverse::Continue
+    }
     fn exit_emphasis(&mut self, _node: &Emphasis) {}
-    fn visit_heading(&mut self, _node: &Heading) {}
+    fn visit_heading(&mut self, _node: &Heading) -> Traverse {
+        Traverse::Continue
+    }
     fn exit_heading(&mut self, _node: &Heading) {}
-    fn visit_hook(&mut self, _node: &Hook) {}
+    fn visit_hook(&mut self, _node: &Hook) -> Traverse {
+        Traverse::Continue
+    }
     fn exit_hook(&mut self, _node: &Hook) {}
-    fn visit_icu(&mut self, _node: &Icu) {}
+    fn visit_icu(&mut self, _node: &Icu) -> Traverse {
+        Traverse::Continue
+    }
     fn exit_icu(&mut self, _node: &Icu) {}
-    fn visit_icu_date(&mut self, _node: &IcuDate) {}
+    fn visit_icu_date(&mut self, _node: &IcuDate) -> Traverse {
+        Traverse::Continue
+    }
     fn exit_icu_date(&mut self, _node: &IcuDate) {}
-    fn visit_icu_date_time_style(&mut self, _node: &IcuDateTimeStyle) {}
+    fn visit_icu_date_time_style(&mut self, _node: &IcuDateTimeStyle) -> Traverse {
+        Traverse::Continue
+    }
     fn exit_icu_date_time_style(&mut self, _node: &IcuDateTimeStyle) {}
-    fn visit_icu_number(&mut self, _node: &IcuNumber) {}
+    fn visit_icu_date_time_component(&mut self, _node: &DateTimeComponent) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_icu_date_time_component(&mut self, _node: &DateTimeComponent) {}
+    /// Called instead of descending into components when a style's skeleton token contains a
+    /// CLDR letter this crate doesn't model, so a validation pass can distinguish "this skeleton
+    /// has no components" from "this skeleton uses an unsupported field" rather than both being
+    /// silently treated the same way.
+    fn visit_icu_date_time_style_error(
+        &mut self,
+        _node: &IcuDateTimeStyle,
+        _error: &UnknownSkeletonField,
+    ) -> Traverse {
+        Traverse::Continue
+    }
+    fn visit_icu_number(&mut self, _node: &IcuNumber) -> Traverse {
+        Traverse::Continue
+    }
     fn exit_icu_number(&mut self, _node: &IcuNumber) {}
-    fn visit_icu_number_style(&mut self, _node: &IcuNumberStyle) {}
+    fn visit_icu_number_style(&mut self, _node: &IcuNumberStyle) -> Traverse {
+        Traverse::Continue
+    }
     fn exit_icu_number_style(&mut self, _node: &IcuNumberStyle) {}
-    fn visit_icu_plural(&mut self, _node: &IcuPlural) {}
+    fn visit_icu_plural(&mut self, _node: &IcuPlural) -> Traverse {
+        Traverse::Continue
+    }
     fn exit_icu_plural(&mut self, _node: &IcuPlural) {}
-    fn visit_icu_plural_arm(&mut self, _node: &IcuPluralArm) {}
+    fn visit_icu_plural_arm(&mut self, _node: &IcuPluralArm) -> Traverse {
+        Traverse::Continue
+    }
     fn exit_icu_plural_arm(&mut self, _node: &IcuPluralArm) {}
-    fn visit_icu_select(&mut self, _node: &IcuSelect) {}
+    fn visit_icu_select(&mut self, _node: &IcuSelect) -> Traverse {
+        Traverse::Continue
+    }
     fn exit_icu_select(&mut self, _node: &IcuSelect) {}
-    fn visit_icu_time(&mut self, _node: &IcuTime) {}
+    fn visit_icu_time(&mut self, _node: &IcuTime) -> Traverse {
+        Traverse::Continue
+    }
     fn exit_icu_time(&mut self, _node: &IcuTime) {}
-    fn visit_icu_variable(&mut self, _node: &IcuVariable) {}
+    fn visit_icu_variable(&mut self, _node: &IcuVariable) -> Traverse {
+        Traverse::Continue
+    }
     fn exit_icu_variable(&mut self, _node: &IcuVariable) {}
-    fn visit_inline_content(&mut self, _node: &InlineContent) {}
+    fn visit_inline_content(&mut self, _node: &InlineContent) -> Traverse {
+        Traverse::Continue
+    }
     fn exit_inline_content(&mut self, _node: &InlineContent) {}
-    fn visit_link(&mut self, _node: &Link) {}
+    fn visit_link(&mut self, _node: &Link) -> Traverse {
+        Traverse::Continue
+    }
     fn exit_link(&mut self, _node: &Link) {}
-    fn visit_link_destination(&mut self, _node: &TextOrPlaceholder) {}
+    fn visit_link_destination(&mut self, _node: &TextOrPlaceholder) -> Traverse {
+        Traverse::Continue
+    }
     fn exit_link_destination(&mut self, _node: &TextOrPlaceholder) {}
-    fn visit_paragraph(&mut self, _node: &Paragraph) {}
+    fn visit_paragraph(&mut self, _node: &Paragraph) -> Traverse {
+        Traverse::Continue
+    }
     fn exit_paragraph(&mut self, _node: &Paragraph) {}
-    fn visit_strikethrough(&mut self, _node: &Strikethrough) {}
+    fn visit_strikethrough(&mut self, _node: &Strikethrough) -> Traverse {
+        Traverse::Continue
+    }
     fn exit_strikethrough(&mut self, _node: &Strikethrough) {}
-    fn visit_strong(&mut self, _node: &Strong) {}
+    fn visit_strong(&mut self, _node: &Strong) -> Traverse {
+        Traverse::Continue
+    }
     fn exit_strong(&mut self, _node: &Strong) {}
-    fn visit_text_or_placeholder(&mut self, _node: &TextOrPlaceholder) {}
+    fn visit_text_or_placeholder(&mut self, _node: &TextOrPlaceholder) -> Traverse {
+        Traverse::Continue
+    }
     fn exit_text_or_placeholder(&mut self, _node: &TextOrPlaceholder) {}
-    fn visit_thematic_break(&mut self) {}
+    fn visit_thematic_break(&mut self) -> Traverse {
+        Traverse::Continue
+    }
     fn exit_thematic_break(&mut self) {}
-    fn visit_hard_line_break(&mut self) {}
+    fn visit_hard_line_break(&mut self) -> Traverse {
+        Traverse::Continue
+    }
     fn exit_hard_line_break(&mut self) {}
-    fn visit_icu_pound(&mut self) {}
+    fn visit_icu_pound(&mut self) -> Traverse {
+        Traverse::Continue
+    }
     fn exit_icu_pound(&mut self) {}
-    fn visit_text(&mut self, _node: &String) {}
+    fn visit_text(&mut self, _node: &String) -> Traverse {
+        Traverse::Continue
+    }
     fn exit_text(&mut self, _node: &String) {}
 }
 
 pub fn visit_with_mut<V: Visitor>(visitor: &mut V, document: &Document) {
-    Traversal::traverse_document(visitor, document);
+    let _ = Traversal::traverse_document(visitor, document);
 }
 
 pub struct Traversal;
 
 impl Traversal {
+    /// Turns a `visit_*` result into the outcome of the node's own traversal when that node has
+    /// no children to skip (e.g. leaf nodes, or nodes whose "children" are fixed-shape fields
+    /// handled inline): `SkipChildren` and `Continue` are equivalent, `Stop` still unwinds.
+    #[inline(always)]
+    fn leaf_result(control: Traverse) -> Traverse {
+        match control {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren | Traverse::Continue => Traverse::Continue,
+        }
+    }
+
     #[inline(always)]
     pub fn traverse_children<V: Visitor, N, F>(
         visitor: &mut V,
         nodes: &Vec<N>,
         mut traverse_func: F,
-    ) where
+    ) -> Traverse
+    where
         V: Visitor,
-        F: FnMut(&mut V, &N) -> (),
+        F: FnMut(&mut V, &N) -> Traverse,
     {
         for child in nodes {
-            traverse_func(visitor, child);
+            if traverse_func(visitor, child) == Traverse::Stop {
+                return Traverse::Stop;
+            }
         }
+        Traverse::Continue
     }
 
     #[inline(always)]
-    pub fn traverse_inline_children<V: Visitor>(visitor: &mut V, children: &Vec<InlineContent>) {
+    pub fn traverse_inline_children<V: Visitor>(
+        visitor: &mut V,
+        children: &Vec<InlineContent>,
+    ) -> Traverse {
         for child in children {
-            Self::traverse_inline_content(visitor, child);
+            if Self::traverse_inline_content(visitor, child) == Traverse::Stop {
+                return Traverse::Stop;
+            }
         }
+        Traverse::Continue
     }
 
-    pub fn traverse_document<V: Visitor>(visitor: &mut V, node: &Document) {
-        visitor.visit_document(node);
-        Self::traverse_children(visitor, node.blocks(), Self::traverse_block_node);
+    pub fn traverse_document<V: Visitor>(visitor: &mut V, node: &Document) -> Traverse {
+        let result = match visitor.visit_document(node) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => {
+                Self::traverse_children(visitor, node.blocks(), Self::traverse_block_node)
+            }
+        };
         visitor.exit_document(node);
+        result
     }
 
-    pub fn traverse_block_node<V: Visitor>(visitor: &mut V, node: &BlockNode) {
-        visitor.visit_block_node(node);
-        match node {
-            BlockNode::Paragraph(paragraph) => Self::traverse_paragraph(visitor, paragraph),
-            BlockNode::Heading(heading) => Self::traverse_heading(visitor, heading),
-            BlockNode::CodeBlock(code_block) => Self::traverse_code_block(visitor, code_block),
-            BlockNode::ThematicBreak => visitor.visit_thematic_break(),
-            BlockNode::InlineContent(inline_content) => {
-                Self::traverse_inline_children(visitor, inline_content)
-            }
-        }
+    pub fn traverse_block_node<V: Visitor>(visitor: &mut V, node: &BlockNode) -> Traverse {
+        let result = match visitor.visit_block_node(node) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => match node {
+                BlockNode::Paragraph(paragraph) => Self::traverse_paragraph(visitor, paragraph),
+                BlockNode::Heading(heading) => Self::traverse_heading(visitor, heading),
+                BlockNode::CodeBlock(code_block) => {
+                    Self::traverse_code_block(visitor, code_block)
+                }
+                BlockNode::ThematicBreak => Self::leaf_result(visitor.visit_thematic_break()),
+                BlockNode::InlineContent(inline_content) => {
+                    Self::traverse_inline_children(visitor, inline_content)
+                }
+            },
+        };
         visitor.exit_block_node(node);
+        result
     }
 
-    pub fn traverse_paragraph<V: Visitor>(visitor: &mut V, node: &Paragraph) {
-        visitor.visit_paragraph(node);
-        Self::traverse_inline_children(visitor, node.content());
+    pub fn traverse_paragraph<V: Visitor>(visitor: &mut V, node: &Paragraph) -> Traverse {
+        let result = match visitor.visit_paragraph(node) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => Self::traverse_inline_children(visitor, node.content()),
+        };
         visitor.exit_paragraph(node);
+        result
     }
 
-    pub fn traverse_inline_content<V: Visitor>(visitor: &mut V, content: &InlineContent) {
-        visitor.visit_inline_content(content);
-        match content {
-            InlineContent::Text(text) => Self::traverse_text(visitor, text),
-            InlineContent::Emphasis(emphasis) => Self::traverse_emphasis(visitor, emphasis),
-            InlineContent::Strong(strong) => Self::traverse_strong(visitor, strong),
-            InlineContent::Link(link) => Self::traverse_link(visitor, link),
-            InlineContent::CodeSpan(code_span) => Self::traverse_code_span(visitor, code_span),
-            InlineContent::Hook(hook) => Self::traverse_hook(visitor, hook),
-            InlineContent::Strikethrough(strikethrough) => {
-                Self::traverse_strikethrough(visitor, strikethrough)
-            }
-            InlineContent::Icu(icu) => Self::traverse_icu(visitor, icu),
-            InlineContent::IcuPound => visitor.visit_icu_pound(),
-            InlineContent::HardLineBreak => visitor.visit_hard_line_break(),
-        }
+    pub fn traverse_inline_content<V: Visitor>(
+        visitor: &mut V,
+        content: &InlineContent,
+    ) -> Traverse {
+        let result = match visitor.visit_inline_content(content) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => match content {
+                InlineContent::Text(text) => Self::traverse_text(visitor, text),
+                InlineContent::Emphasis(emphasis) => Self::traverse_emphasis(visitor, emphasis),
+                InlineContent::Strong(strong) => Self::traverse_strong(visitor, strong),
+                InlineContent::Link(link) => Self::traverse_link(visitor, link),
+                InlineContent::CodeSpan(code_span) => {
+                    Self::traverse_code_span(visitor, code_span)
+                }
+                InlineContent::Hook(hook) => Self::traverse_hook(visitor, hook),
+                InlineContent::Strikethrough(strikethrough) => {
+                    Self::traverse_strikethrough(visitor, strikethrough)
+                }
+                InlineContent::Icu(icu) => Self::traverse_icu(visitor, icu),
+                InlineContent::IcuPound => Self::leaf_result(visitor.visit_icu_pound()),
+                InlineContent::HardLineBreak => Self::leaf_result(visitor.visit_hard_line_break()),
+            },
+        };
         visitor.exit_inline_content(content);
+        result
     }
 
-    pub fn traverse_heading<V: Visitor>(visitor: &mut V, heading: &Heading) {
-        visitor.visit_heading(heading);
-        Self::traverse_inline_children(visitor, heading.content());
+    pub fn traverse_heading<V: Visitor>(visitor: &mut V, heading: &Heading) -> Traverse {
+        let result = match visitor.visit_heading(heading) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => Self::traverse_inline_children(visitor, heading.content()),
+        };
         visitor.exit_heading(heading);
+        result
     }
 
-    pub fn traverse_code_block<V: Visitor>(visitor: &mut V, code_block: &CodeBlock) {
-        visitor.visit_code_block(code_block);
+    pub fn traverse_code_block<V: Visitor>(visitor: &mut V, code_block: &CodeBlock) -> Traverse {
+        let result = Self::leaf_result(visitor.visit_code_block(code_block));
         visitor.exit_code_block(code_block);
+        result
     }
 
-    pub fn traverse_text<V: Visitor>(visitor: &mut V, text: &String) {
-        visitor.visit_text(text);
+    pub fn traverse_text<V: Visitor>(visitor: &mut V, text: &String) -> Traverse {
+        let result = Self::leaf_result(visitor.visit_text(text));
         visitor.exit_text(text);
+        result
     }
 
-    pub fn traverse_emphasis<V: Visitor>(visitor: &mut V, emphasis: &Emphasis) {
-        visitor.visit_emphasis(emphasis);
-        Self::traverse_inline_children(visitor, emphasis.content());
+    pub fn traverse_emphasis<V: Visitor>(visitor: &mut V, emphasis: &Emphasis) -> Traverse {
+        let result = match visitor.visit_emphasis(emphasis) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => Self::traverse_inline_children(visitor, emphasis.content()),
+        };
         visitor.exit_emphasis(emphasis);
+        result
     }
 
-    pub fn traverse_strong<V: Visitor>(visitor: &mut V, strong: &Strong) {
-        visitor.visit_strong(strong);
-        Self::traverse_inline_children(visitor, strong.content());
+    pub fn traverse_strong<V: Visitor>(visitor: &mut V, strong: &Strong) -> Traverse {
+        let result = match visitor.visit_strong(strong) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => Self::traverse_inline_children(visitor, strong.content()),
+        };
         visitor.exit_strong(strong);
+        result
     }
 
-    pub fn traverse_strikethrough<V: Visitor>(visitor: &mut V, strikethrough: &Strikethrough) {
-        visitor.visit_strikethrough(strikethrough);
-        Self::traverse_inline_children(visitor, strikethrough.content());
+    pub fn traverse_strikethrough<V: Visitor>(
+        visitor: &mut V,
+        strikethrough: &Strikethrough,
+    ) -> Traverse {
+        let result = match visitor.visit_strikethrough(strikethrough) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => {
+                Self::traverse_inline_children(visitor, strikethrough.content())
+            }
+        };
         visitor.exit_strikethrough(strikethrough);
+        result
     }
 
-    pub fn traverse_link<V: Visitor>(visitor: &mut V, link: &Link) {
-        visitor.visit_link(link);
-        Self::traverse_inline_children(visitor, link.label());
-        Self::traverse_link_destination(visitor, link.destination());
+    pub fn traverse_link<V: Visitor>(visitor: &mut V, link: &Link) -> Traverse {
+        let result = match visitor.visit_link(link) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => {
+                match Self::traverse_inline_children(visitor, link.label()) {
+                    Traverse::Stop => Traverse::Stop,
+                    Traverse::Continue | Traverse::SkipChildren => {
+                        Self::traverse_link_destination(visitor, link.destination())
+                    }
+                }
+            }
+        };
         visitor.exit_link(link);
+        result
     }
 
-    pub fn traverse_link_destination<V: Visitor>(visitor: &mut V, handler: &TextOrPlaceholder) {
-        visitor.visit_link_destination(handler);
-        // Only traversing placeholders separately, since Text and Handler are just String values
-        // that are _not_ visible content in this context.
-        match handler {
-            TextOrPlaceholder::Placeholder(placeholder) => Self::traverse_icu(visitor, placeholder),
-            _ => {}
-        }
+    pub fn traverse_link_destination<V: Visitor>(
+        visitor: &mut V,
+        handler: &TextOrPlaceholder,
+    ) -> Traverse {
+        let result = match visitor.visit_link_destination(handler) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            // Only traversing placeholders separately, since Text and Handler are just String
+            // values that are _not_ visible content in this context.
+            Traverse::Continue => match handler {
+                TextOrPlaceholder::Placeholder(placeholder) => {
+                    Self::traverse_icu(visitor, placeholder)
+                }
+                _ => Traverse::Continue,
+            },
+        };
         visitor.exit_link_destination(handler);
+        result
     }
 
-    pub fn traverse_hook<V: Visitor>(visitor: &mut V, hook: &Hook) {
-        visitor.visit_hook(hook);
-        Self::traverse_inline_children(visitor, hook.content());
+    pub fn traverse_hook<V: Visitor>(visitor: &mut V, hook: &Hook) -> Traverse {
+        let result = match visitor.visit_hook(hook) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => Self::traverse_inline_children(visitor, hook.content()),
+        };
         visitor.exit_hook(hook);
+        result
     }
 
-    pub fn traverse_code_span<V: Visitor>(visitor: &mut V, code_span: &CodeSpan) {
-        visitor.visit_code_span(code_span);
+    pub fn traverse_code_span<V: Visitor>(visitor: &mut V, code_span: &CodeSpan) -> Traverse {
+        let result = Self::leaf_result(visitor.visit_code_span(code_span));
         visitor.exit_code_span(code_span);
+        result
     }
 
-    pub fn traverse_icu<V: Visitor>(visitor: &mut V, icu: &Icu) {
-        visitor.visit_icu(icu);
-        match icu {
-            Icu::IcuVariable(variable) => Self::traverse_icu_variable(visitor, variable),
-            Icu::IcuPlural(plural) => Self::traverse_icu_plural(visitor, plural),
-            Icu::IcuSelect(select) => Self::traverse_icu_select(visitor, select),
-            Icu::IcuDate(date) => Self::traverse_icu_date(visitor, date),
-            Icu::IcuTime(time) => Self::traverse_icu_time(visitor, time),
-            Icu::IcuNumber(number) => Self::traverse_icu_number(visitor, number),
-        }
+    pub fn traverse_icu<V: Visitor>(visitor: &mut V, icu: &Icu) -> Traverse {
+        let result = match visitor.visit_icu(icu) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => match icu {
+                Icu::IcuVariable(variable) => Self::traverse_icu_variable(visitor, variable),
+                Icu::IcuPlural(plural) => Self::traverse_icu_plural(visitor, plural),
+                Icu::IcuSelect(select) => Self::traverse_icu_select(visitor, select),
+                Icu::IcuDate(date) => Self::traverse_icu_date(visitor, date),
+                Icu::IcuTime(time) => Self::traverse_icu_time(visitor, time),
+                Icu::IcuNumber(number) => Self::traverse_icu_number(visitor, number),
+            },
+        };
         visitor.exit_icu(icu);
+        result
     }
 
-    pub fn traverse_icu_variable<V: Visitor>(visitor: &mut V, variable: &IcuVariable) {
-        visitor.visit_icu_variable(variable);
+    pub fn traverse_icu_variable<V: Visitor>(visitor: &mut V, variable: &IcuVariable) -> Traverse {
+        let result = Self::leaf_result(visitor.visit_icu_variable(variable));
         visitor.exit_icu_variable(variable);
+        result
     }
 
-    pub fn traverse_icu_plural<V: Visitor>(visitor: &mut V, plural: &IcuPlural) {
-        visitor.visit_icu_plural(plural);
-        Self::traverse_icu_variable(visitor, plural.variable());
-        Self::traverse_children(visitor, plural.arms(), Self::traverse_icu_plural_arm);
+    pub fn traverse_icu_plural<V: Visitor>(visitor: &mut V, plural: &IcuPlural) -> Traverse {
+        let result = match visitor.visit_icu_plural(plural) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => {
+                match Self::traverse_icu_variable(visitor, plural.variable()) {
+                    Traverse::Stop => Traverse::Stop,
+                    Traverse::Continue | Traverse::SkipChildren => {
+                        Self::traverse_children(visitor, plural.arms(), Self::traverse_icu_plural_arm)
+                    }
+                }
+            }
+        };
         visitor.exit_icu_plural(plural);
+        result
     }
 
-    pub fn traverse_icu_plural_arm<V: Visitor>(visitor: &mut V, arm: &IcuPluralArm) {
-        visitor.visit_icu_plural_arm(arm);
-        Self::traverse_inline_children(visitor, arm.content());
+    pub fn traverse_icu_plural_arm<V: Visitor>(visitor: &mut V, arm: &IcuPluralArm) -> Traverse {
+        let result = match visitor.visit_icu_plural_arm(arm) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => Self::traverse_inline_children(visitor, arm.content()),
+        };
         visitor.exit_icu_plural_arm(arm);
+        result
     }
 
-    pub fn traverse_icu_select<V: Visitor>(visitor: &mut V, select: &IcuSelect) {
-        visitor.visit_icu_select(select);
-        Self::traverse_icu_variable(visitor, select.variable());
-        Self::traverse_children(visitor, select.arms(), Self::traverse_icu_plural_arm);
+    pub fn traverse_icu_select<V: Visitor>(visitor: &mut V, select: &IcuSelect) -> Traverse {
+        let result = match visitor.visit_icu_select(select) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => {
+                match Self::traverse_icu_variable(visitor, select.variable()) {
+                    Traverse::Stop => Traverse::Stop,
+                    Traverse::Continue | Traverse::SkipChildren => {
+                        Self::traverse_children(visitor, select.arms(), Self::traverse_icu_plural_arm)
+                    }
+                }
+            }
+        };
         visitor.exit_icu_select(select);
+        result
     }
 
-    pub fn traverse_icu_date<V: Visitor>(visitor: &mut V, date: &IcuDate) {
-        visitor.visit_icu_date(date);
-        Self::traverse_icu_variable(visitor, date.variable());
-        if let Some(style) = date.style.as_ref() {
-            Self::traverse_icu_date_time_style(visitor, style);
-        }
+    pub fn traverse_icu_date<V: Visitor>(visitor: &mut V, date: &IcuDate) -> Traverse {
+        let result = match visitor.visit_icu_date(date) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => {
+                match Self::traverse_icu_variable(visitor, date.variable()) {
+                    Traverse::Stop => Traverse::Stop,
+                    Traverse::Continue | Traverse::SkipChildren => match date.style.as_ref() {
+                        Some(style) => Self::traverse_icu_date_time_style(visitor, style),
+                        None => Traverse::Continue,
+                    },
+                }
+            }
+        };
         visitor.exit_icu_date(date);
+        result
     }
 
-    pub fn traverse_icu_date_time_style<V: Visitor>(visitor: &mut V, style: &IcuDateTimeStyle) {
-        visitor.visit_icu_date_time_style(style);
+    pub fn traverse_icu_date_time_style<V: Visitor>(
+        visitor: &mut V,
+        style: &IcuDateTimeStyle,
+    ) -> Traverse {
+        let result = match visitor.visit_icu_date_time_style(style) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => match style.components() {
+                Ok(components) => Self::traverse_children(
+                    visitor,
+                    &components,
+                    Self::traverse_icu_date_time_component,
+                ),
+                // The token came from already-parsed source, so an unknown field here means the
+                // skeleton used CLDR letters this crate doesn't model; surface it through a
+                // dedicated callback instead of treating it the same as a style with no
+                // components, so validation passes can tell the two apart.
+                Err(err) => match visitor.visit_icu_date_time_style_error(style, &err) {
+                    Traverse::Stop => Traverse::Stop,
+                    Traverse::Continue | Traverse::SkipChildren => Traverse::Continue,
+                },
+            },
+        };
         visitor.exit_icu_date_time_style(style);
+        result
     }
 
-    pub fn traverse_icu_time<V: Visitor>(visitor: &mut V, time: &IcuTime) {
-        visitor.visit_icu_time(time);
-        Self::traverse_icu_variable(visitor, time.variable());
-        if let Some(style) = time.style.as_ref() {
-            Self::traverse_icu_date_time_style(visitor, style);
-        }
+    pub fn traverse_icu_date_time_component<V: Visitor>(
+        visitor: &mut V,
+        component: &DateTimeComponent,
+    ) -> Traverse {
+        let result = Self::leaf_result(visitor.visit_icu_date_time_component(component));
+        visitor.exit_icu_date_time_component(component);
+        result
+    }
+
+    pub fn traverse_icu_time<V: Visitor>(visitor: &mut V, time: &IcuTime) -> Traverse {
+        let result = match visitor.visit_icu_time(time) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => {
+                match Self::traverse_icu_variable(visitor, time.variable()) {
+                    Traverse::Stop => Traverse::Stop,
+                    Traverse::Continue | Traverse::SkipChildren => match time.style.as_ref() {
+                        Some(style) => Self::traverse_icu_date_time_style(visitor, style),
+                        None => Traverse::Continue,
+                    },
+                }
+            }
+        };
         visitor.exit_icu_time(time);
+        result
     }
 
-    pub fn traverse_icu_number<V: Visitor>(visitor: &mut V, number: &IcuNumber) {
-        visitor.visit_icu_number(number);
-        if let Some(style) = number.style.as_ref() {
-            Self::traverse_icu_number_style(visitor, style);
-        }
+    pub fn traverse_icu_number<V: Visitor>(visitor: &mut V, number: &IcuNumber) -> Traverse {
+        let result = match visitor.visit_icu_number(number) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => match number.style.as_ref() {
+                Some(style) => Self::traverse_icu_number_style(visitor, style),
+                None => Traverse::Continue,
+            },
+        };
         visitor.exit_icu_number(number);
+        result
     }
 
-    pub fn traverse_icu_number_style<V: Visitor>(visitor: &mut V, style: &IcuNumberStyle) {
-        visitor.visit_icu_number_style(style);
+    pub fn traverse_icu_number_style<V: Visitor>(
+        visitor: &mut V,
+        style: &IcuNumberStyle,
+    ) -> Traverse {
+        let result = Self::leaf_result(visitor.visit_icu_number_style(style));
         visitor.exit_icu_number_style(style);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A visitor that records every `Text` node it's shown, skips the inline content of any
+    /// block node containing `skip_marker`, and stops the whole traversal at `stop_at`.
+    struct RecordingVisitor {
+        seen: Vec<String>,
+        skip_marker: Option<&'static str>,
+        stop_at: Option<&'static str>,
+    }
+
+    impl Visitor for RecordingVisitor {
+        fn visit_block_node(&mut self, node: &BlockNode) -> Traverse {
+            if let BlockNode::InlineContent(items) = node {
+                let has_marker = items.iter().any(|item| {
+                    matches!(item, InlineContent::Text(text) if Some(text.as_str()) == self.skip_marker)
+                });
+                if has_marker {
+                    return Traverse::SkipChildren;
+                }
+            }
+            Traverse::Continue
+        }
+
+        fn visit_text(&mut self, node: &String) -> Traverse {
+            self.seen.push(node.clone());
+            if self.stop_at == Some(node.as_str()) {
+                Traverse::Stop
+            } else {
+                Traverse::Continue
+            }
+        }
+    }
+
+    fn text_block(text: &str) -> BlockNode {
+        BlockNode::InlineContent(vec![InlineContent::Text(text.to_string())])
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn skip_children_prunes_the_subtree_but_keeps_walking_siblings() {
+        let blocks = vec![text_block("skip me"), text_block("after")];
+        let mut visitor = RecordingVisitor {
+            seen: Vec::new(),
+            skip_marker: Some("skip me"),
+            stop_at: None,
+        };
+        Traversal::traverse_children(&mut visitor, &blocks, Traversal::traverse_block_node);
+        assert_eq!(visitor.seen, vec!["after".to_string()]);
+    }
+
+    #[test]
+    fn stop_short_circuits_remaining_siblings() {
+        let blocks = vec![
+            text_block("first"),
+            text_block("stop here"),
+            text_block("never seen"),
+        ];
+        let mut visitor = RecordingVisitor {
+            seen: Vec::new(),
+            skip_marker: None,
+            stop_at: Some("stop here"),
+        };
+        let result =
+            Traversal::traverse_children(&mut visitor, &blocks, Traversal::traverse_block_node);
+        assert_eq!(result, Traverse::Stop);
+        assert_eq!(
+            visitor.seen,
+            vec!["first".to_string(), "stop here".to_string()]
+        );
+    }
+}
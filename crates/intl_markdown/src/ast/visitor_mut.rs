@@ -0,0 +1,580 @@
+use crate::{
+    BlockNode, CodeBlock, CodeSpan, Document, Emphasis, Heading, Hook, Icu, IcuDate,
+    IcuDateTimeStyle, IcuNumber, IcuNumberStyle, IcuPlural, IcuPluralArm, IcuSelect, IcuTime,
+    IcuVariable, InlineContent, Link, Paragraph, Strikethrough, Strong, TextOrPlaceholder,
+};
+
+use super::visitor::{Traversal, Traverse};
+
+/// Mirror of [`Visitor`](super::visitor::Visitor) whose callbacks receive `&mut` nodes instead
+/// of shared references, allowing a traversal to rewrite the tree in place (e.g. normalizing
+/// link destinations or renaming ICU variables) rather than requiring callers to rebuild it by
+/// hand from a read-only walk. `visit_*` methods return [`Traverse`] with the same pruning and
+/// short-circuiting semantics as the read-only `Visitor`.
+pub trait VisitorMut {
+    fn visit_block_node(&mut self, _node: &mut BlockNode) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_block_node(&mut self, _node: &mut BlockNode) {}
+    fn visit_code_block(&mut self, _node: &mut CodeBlock) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_code_block(&mut self, _node: &mut CodeBlock) {}
+    fn visit_code_span(&mut self, _node: &mut CodeSpan) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_code_span(&mut self, _node: &mut CodeSpan) {}
+    fn visit_document(&mut self, _node: &mut Document) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_document(&mut self, _node: &mut Document) {}
+    fn visit_emphasis(&mut self, _node: &mut Emphasis) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_emphasis(&mut self, _node: &mut Emphasis) {}
+    fn visit_heading(&mut self, _node: &mut Heading) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_heading(&mut self, _node: &mut Heading) {}
+    fn visit_hook(&mut self, _node: &mut Hook) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_hook(&mut self, _node: &mut Hook) {}
+    fn visit_icu(&mut self, _node: &mut Icu) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_icu(&mut self, _node: &mut Icu) {}
+    fn visit_icu_date(&mut self, _node: &mut IcuDate) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_icu_date(&mut self, _node: &mut IcuDate) {}
+    fn visit_icu_date_time_style(&mut self, _node: &mut IcuDateTimeStyle) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_icu_date_time_style(&mut self, _node: &mut IcuDateTimeStyle) {}
+    fn visit_icu_number(&mut self, _node: &mut IcuNumber) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_icu_number(&mut self, _node: &mut IcuNumber) {}
+    fn visit_icu_number_style(&mut self, _node: &mut IcuNumberStyle) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_icu_number_style(&mut self, _node: &mut IcuNumberStyle) {}
+    fn visit_icu_plural(&mut self, _node: &mut IcuPlural) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_icu_plural(&mut self, _node: &mut IcuPlural) {}
+    fn visit_icu_plural_arm(&mut self, _node: &mut IcuPluralArm) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_icu_plural_arm(&mut self, _node: &mut IcuPluralArm) {}
+    fn visit_icu_select(&mut self, _node: &mut IcuSelect) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_icu_select(&mut self, _node: &mut IcuSelect) {}
+    fn visit_icu_time(&mut self, _node: &mut IcuTime) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_icu_time(&mut self, _node: &mut IcuTime) {}
+    fn visit_icu_variable(&mut self, _node: &mut IcuVariable) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_icu_variable(&mut self, _node: &mut IcuVariable) {}
+    fn visit_inline_content(&mut self, _node: &mut InlineContent) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_inline_content(&mut self, _node: &mut InlineContent) {}
+    fn visit_link(&mut self, _node: &mut Link) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_link(&mut self, _node: &mut Link) {}
+    fn visit_link_destination(&mut self, _node: &mut TextOrPlaceholder) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_link_destination(&mut self, _node: &mut TextOrPlaceholder) {}
+    fn visit_paragraph(&mut self, _node: &mut Paragraph) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_paragraph(&mut self, _node: &mut Paragraph) {}
+    fn visit_strikethrough(&mut self, _node: &mut Strikethrough) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_strikethrough(&mut self, _node: &mut Strikethrough) {}
+    fn visit_strong(&mut self, _node: &mut Strong) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_strong(&mut self, _node: &mut Strong) {}
+    fn visit_text_or_placeholder(&mut self, _node: &mut TextOrPlaceholder) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_text_or_placeholder(&mut self, _node: &mut TextOrPlaceholder) {}
+    fn visit_thematic_break(&mut self) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_thematic_break(&mut self) {}
+    fn visit_hard_line_break(&mut self) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_hard_line_break(&mut self) {}
+    fn visit_icu_pound(&mut self) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_icu_pound(&mut self) {}
+    fn visit_text(&mut self, _node: &mut String) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_text(&mut self, _node: &mut String) {}
+}
+
+/// Runs `visitor` mutably over `document`, distinct from
+/// [`visitor::visit_with_mut`](super::visitor::visit_with_mut), which only mutates the visitor
+/// and leaves the tree untouched.
+pub fn visit_document_mut<V: VisitorMut>(visitor: &mut V, document: &mut Document) {
+    let _ = Traversal::traverse_document_mut(visitor, document);
+}
+
+impl Traversal {
+    /// Turns a `visit_*` result into the outcome of the node's own traversal when that node has
+    /// no children to skip (e.g. leaf nodes, or nodes whose "children" are fixed-shape fields
+    /// handled inline): `SkipChildren` and `Continue` are equivalent, `Stop` still unwinds.
+    #[inline(always)]
+    fn leaf_result_mut(control: Traverse) -> Traverse {
+        match control {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren | Traverse::Continue => Traverse::Continue,
+        }
+    }
+
+    #[inline(always)]
+    pub fn traverse_children_mut<V: VisitorMut, N, F>(
+        visitor: &mut V,
+        nodes: &mut Vec<N>,
+        mut traverse_func: F,
+    ) -> Traverse
+    where
+        V: VisitorMut,
+        F: FnMut(&mut V, &mut N) -> Traverse,
+    {
+        for child in nodes {
+            if traverse_func(visitor, child) == Traverse::Stop {
+                return Traverse::Stop;
+            }
+        }
+        Traverse::Continue
+    }
+
+    #[inline(always)]
+    pub fn traverse_inline_children_mut<V: VisitorMut>(
+        visitor: &mut V,
+        children: &mut Vec<InlineContent>,
+    ) -> Traverse {
+        for child in children {
+            if Self::traverse_inline_content_mut(visitor, child) == Traverse::Stop {
+                return Traverse::Stop;
+            }
+        }
+        Traverse::Continue
+    }
+
+    pub fn traverse_document_mut<V: VisitorMut>(visitor: &mut V, node: &mut Document) -> Traverse {
+        let result = match visitor.visit_document(node) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => Self::traverse_children_mut(
+                visitor,
+                node.blocks_mut(),
+                Self::traverse_block_node_mut,
+            ),
+        };
+        visitor.exit_document(node);
+        result
+    }
+
+    pub fn traverse_block_node_mut<V: VisitorMut>(
+        visitor: &mut V,
+        node: &mut BlockNode,
+    ) -> Traverse {
+        let result = match visitor.visit_block_node(node) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => match node {
+                BlockNode::Paragraph(paragraph) => Self::traverse_paragraph_mut(visitor, paragraph),
+                BlockNode::Heading(heading) => Self::traverse_heading_mut(visitor, heading),
+                BlockNode::CodeBlock(code_block) => {
+                    Self::traverse_code_block_mut(visitor, code_block)
+                }
+                BlockNode::ThematicBreak => Self::leaf_result_mut(visitor.visit_thematic_break()),
+                BlockNode::InlineContent(inline_content) => {
+                    Self::traverse_inline_children_mut(visitor, inline_content)
+                }
+            },
+        };
+        visitor.exit_block_node(node);
+        result
+    }
+
+    pub fn traverse_paragraph_mut<V: VisitorMut>(visitor: &mut V, node: &mut Paragraph) -> Traverse {
+        let result = match visitor.visit_paragraph(node) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => Self::traverse_inline_children_mut(visitor, node.content_mut()),
+        };
+        visitor.exit_paragraph(node);
+        result
+    }
+
+    pub fn traverse_inline_content_mut<V: VisitorMut>(
+        visitor: &mut V,
+        content: &mut InlineContent,
+    ) -> Traverse {
+        let result = match visitor.visit_inline_content(content) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => match content {
+                InlineContent::Text(text) => Self::traverse_text_mut(visitor, text),
+                InlineContent::Emphasis(emphasis) => {
+                    Self::traverse_emphasis_mut(visitor, emphasis)
+                }
+                InlineContent::Strong(strong) => Self::traverse_strong_mut(visitor, strong),
+                InlineContent::Link(link) => Self::traverse_link_mut(visitor, link),
+                InlineContent::CodeSpan(code_span) => {
+                    Self::traverse_code_span_mut(visitor, code_span)
+                }
+                InlineContent::Hook(hook) => Self::traverse_hook_mut(visitor, hook),
+                InlineContent::Strikethrough(strikethrough) => {
+                    Self::traverse_strikethrough_mut(visitor, strikethrough)
+                }
+                InlineContent::Icu(icu) => Self::traverse_icu_mut(visitor, icu),
+                InlineContent::IcuPound => Self::leaf_result_mut(visitor.visit_icu_pound()),
+                InlineContent::HardLineBreak => {
+                    Self::leaf_result_mut(visitor.visit_hard_line_break())
+                }
+            },
+        };
+        visitor.exit_inline_content(content);
+        result
+    }
+
+    pub fn traverse_heading_mut<V: VisitorMut>(visitor: &mut V, heading: &mut Heading) -> Traverse {
+        let result = match visitor.visit_heading(heading) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => Self::traverse_inline_children_mut(visitor, heading.content_mut()),
+        };
+        visitor.exit_heading(heading);
+        result
+    }
+
+    pub fn traverse_code_block_mut<V: VisitorMut>(
+        visitor: &mut V,
+        code_block: &mut CodeBlock,
+    ) -> Traverse {
+        let result = Self::leaf_result_mut(visitor.visit_code_block(code_block));
+        visitor.exit_code_block(code_block);
+        result
+    }
+
+    pub fn traverse_text_mut<V: VisitorMut>(visitor: &mut V, text: &mut String) -> Traverse {
+        let result = Self::leaf_result_mut(visitor.visit_text(text));
+        visitor.exit_text(text);
+        result
+    }
+
+    pub fn traverse_emphasis_mut<V: VisitorMut>(
+        visitor: &mut V,
+        emphasis: &mut Emphasis,
+    ) -> Traverse {
+        let result = match visitor.visit_emphasis(emphasis) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => {
+                Self::traverse_inline_children_mut(visitor, emphasis.content_mut())
+            }
+        };
+        visitor.exit_emphasis(emphasis);
+        result
+    }
+
+    pub fn traverse_strong_mut<V: VisitorMut>(visitor: &mut V, strong: &mut Strong) -> Traverse {
+        let result = match visitor.visit_strong(strong) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => Self::traverse_inline_children_mut(visitor, strong.content_mut()),
+        };
+        visitor.exit_strong(strong);
+        result
+    }
+
+    pub fn traverse_strikethrough_mut<V: VisitorMut>(
+        visitor: &mut V,
+        strikethrough: &mut Strikethrough,
+    ) -> Traverse {
+        let result = match visitor.visit_strikethrough(strikethrough) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => {
+                Self::traverse_inline_children_mut(visitor, strikethrough.content_mut())
+            }
+        };
+        visitor.exit_strikethrough(strikethrough);
+        result
+    }
+
+    pub fn traverse_link_mut<V: VisitorMut>(visitor: &mut V, link: &mut Link) -> Traverse {
+        let result = match visitor.visit_link(link) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => {
+                match Self::traverse_inline_children_mut(visitor, link.label_mut()) {
+                    Traverse::Stop => Traverse::Stop,
+                    Traverse::Continue | Traverse::SkipChildren => {
+                        Self::traverse_link_destination_mut(visitor, link.destination_mut())
+                    }
+                }
+            }
+        };
+        visitor.exit_link(link);
+        result
+    }
+
+    pub fn traverse_link_destination_mut<V: VisitorMut>(
+        visitor: &mut V,
+        handler: &mut TextOrPlaceholder,
+    ) -> Traverse {
+        let result = match visitor.visit_link_destination(handler) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            // Only traversing placeholders separately, since Text and Handler are just String
+            // values that are _not_ visible content in this context.
+            Traverse::Continue => match handler {
+                TextOrPlaceholder::Placeholder(placeholder) => {
+                    Self::traverse_icu_mut(visitor, placeholder)
+                }
+                _ => Traverse::Continue,
+            },
+        };
+        visitor.exit_link_destination(handler);
+        result
+    }
+
+    pub fn traverse_hook_mut<V: VisitorMut>(visitor: &mut V, hook: &mut Hook) -> Traverse {
+        let result = match visitor.visit_hook(hook) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => Self::traverse_inline_children_mut(visitor, hook.content_mut()),
+        };
+        visitor.exit_hook(hook);
+        result
+    }
+
+    pub fn traverse_code_span_mut<V: VisitorMut>(
+        visitor: &mut V,
+        code_span: &mut CodeSpan,
+    ) -> Traverse {
+        let result = Self::leaf_result_mut(visitor.visit_code_span(code_span));
+        visitor.exit_code_span(code_span);
+        result
+    }
+
+    pub fn traverse_icu_mut<V: VisitorMut>(visitor: &mut V, icu: &mut Icu) -> Traverse {
+        let result = match visitor.visit_icu(icu) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => match icu {
+                Icu::IcuVariable(variable) => Self::traverse_icu_variable_mut(visitor, variable),
+                Icu::IcuPlural(plural) => Self::traverse_icu_plural_mut(visitor, plural),
+                Icu::IcuSelect(select) => Self::traverse_icu_select_mut(visitor, select),
+                Icu::IcuDate(date) => Self::traverse_icu_date_mut(visitor, date),
+                Icu::IcuTime(time) => Self::traverse_icu_time_mut(visitor, time),
+                Icu::IcuNumber(number) => Self::traverse_icu_number_mut(visitor, number),
+            },
+        };
+        visitor.exit_icu(icu);
+        result
+    }
+
+    pub fn traverse_icu_variable_mut<V: VisitorMut>(
+        visitor: &mut V,
+        variable: &mut IcuVariable,
+    ) -> Traverse {
+        let result = Self::leaf_result_mut(visitor.visit_icu_variable(variable));
+        visitor.exit_icu_variable(variable);
+        result
+    }
+
+    pub fn traverse_icu_plural_mut<V: VisitorMut>(
+        visitor: &mut V,
+        plural: &mut IcuPlural,
+    ) -> Traverse {
+        let result = match visitor.visit_icu_plural(plural) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => {
+                match Self::traverse_icu_variable_mut(visitor, plural.variable_mut()) {
+                    Traverse::Stop => Traverse::Stop,
+                    Traverse::Continue | Traverse::SkipChildren => Self::traverse_children_mut(
+                        visitor,
+                        plural.arms_mut(),
+                        Self::traverse_icu_plural_arm_mut,
+                    ),
+                }
+            }
+        };
+        visitor.exit_icu_plural(plural);
+        result
+    }
+
+    pub fn traverse_icu_plural_arm_mut<V: VisitorMut>(
+        visitor: &mut V,
+        arm: &mut IcuPluralArm,
+    ) -> Traverse {
+        let result = match visitor.visit_icu_plural_arm(arm) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => Self::traverse_inline_children_mut(visitor, arm.content_mut()),
+        };
+        visitor.exit_icu_plural_arm(arm);
+        result
+    }
+
+    pub fn traverse_icu_select_mut<V: VisitorMut>(
+        visitor: &mut V,
+        select: &mut IcuSelect,
+    ) -> Traverse {
+        let result = match visitor.visit_icu_select(select) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => {
+                match Self::traverse_icu_variable_mut(visitor, select.variable_mut()) {
+                    Traverse::Stop => Traverse::Stop,
+                    Traverse::Continue | Traverse::SkipChildren => Self::traverse_children_mut(
+                        visitor,
+                        select.arms_mut(),
+                        Self::traverse_icu_plural_arm_mut,
+                    ),
+                }
+            }
+        };
+        visitor.exit_icu_select(select);
+        result
+    }
+
+    pub fn traverse_icu_date_mut<V: VisitorMut>(visitor: &mut V, date: &mut IcuDate) -> Traverse {
+        let result = match visitor.visit_icu_date(date) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => {
+                match Self::traverse_icu_variable_mut(visitor, date.variable_mut()) {
+                    Traverse::Stop => Traverse::Stop,
+                    Traverse::Continue | Traverse::SkipChildren => match date.style.as_mut() {
+                        Some(style) => Self::traverse_icu_date_time_style_mut(visitor, style),
+                        None => Traverse::Continue,
+                    },
+                }
+            }
+        };
+        visitor.exit_icu_date(date);
+        result
+    }
+
+    /// Unlike the read-only [`Traversal::traverse_icu_date_time_style`], this does not descend
+    /// into the style's parsed components: `IcuDateTimeStyle::components()` always re-parses the
+    /// raw skeleton token into a fresh owned `Vec`, and there is no `components_mut()` or setter
+    /// to write edits back to the style. Until that exists, treat the style as a leaf rather than
+    /// handing out `&mut DateTimeComponent`s that silently vanish when the traversal returns.
+    pub fn traverse_icu_date_time_style_mut<V: VisitorMut>(
+        visitor: &mut V,
+        style: &mut IcuDateTimeStyle,
+    ) -> Traverse {
+        let result = Self::leaf_result_mut(visitor.visit_icu_date_time_style(style));
+        visitor.exit_icu_date_time_style(style);
+        result
+    }
+
+    pub fn traverse_icu_time_mut<V: VisitorMut>(visitor: &mut V, time: &mut IcuTime) -> Traverse {
+        let result = match visitor.visit_icu_time(time) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => {
+                match Self::traverse_icu_variable_mut(visitor, time.variable_mut()) {
+                    Traverse::Stop => Traverse::Stop,
+                    Traverse::Continue | Traverse::SkipChildren => match time.style.as_mut() {
+                        Some(style) => Self::traverse_icu_date_time_style_mut(visitor, style),
+                        None => Traverse::Continue,
+                    },
+                }
+            }
+        };
+        visitor.exit_icu_time(time);
+        result
+    }
+
+    pub fn traverse_icu_number_mut<V: VisitorMut>(
+        visitor: &mut V,
+        number: &mut IcuNumber,
+    ) -> Traverse {
+        let result = match visitor.visit_icu_number(number) {
+            Traverse::Stop => Traverse::Stop,
+            Traverse::SkipChildren => Traverse::Continue,
+            Traverse::Continue => match number.style.as_mut() {
+                Some(style) => Self::traverse_icu_number_style_mut(visitor, style),
+                None => Traverse::Continue,
+            },
+        };
+        visitor.exit_icu_number(number);
+        result
+    }
+
+    pub fn traverse_icu_number_style_mut<V: VisitorMut>(
+        visitor: &mut V,
+        style: &mut IcuNumberStyle,
+    ) -> Traverse {
+        let result = Self::leaf_result_mut(visitor.visit_icu_number_style(style));
+        visitor.exit_icu_number_style(style);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Uppercases every `Text` node it's shown, in place.
+    struct Uppercase;
+
+    impl VisitorMut for Uppercase {
+        fn visit_text(&mut self, node: &mut String) -> Traverse {
+            *node = node.to_uppercase();
+            Traverse::Continue
+        }
+    }
+
+    #[test]
+    fn visit_document_mut_rewrites_text_nodes_in_place() {
+        let mut document = Document::new(vec![
+            BlockNode::InlineContent(vec![InlineContent::Text("hello".to_string())]),
+            BlockNode::InlineContent(vec![
+                InlineContent::Text("world".to_string()),
+                InlineContent::IcuPound,
+            ]),
+        ]);
+        let mut visitor = Uppercase;
+        visit_document_mut(&mut visitor, &mut document);
+
+        let rendered: Vec<&str> = document
+            .blocks()
+            .iter()
+            .flat_map(|block| match block {
+                BlockNode::InlineContent(items) => items
+                    .iter()
+                    .filter_map(|item| match item {
+                        InlineContent::Text(text) => Some(text.as_str()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>(),
+                _ => Vec::new(),
+            })
+            .collect();
+        assert_eq!(rendered, vec!["HELLO", "WORLD"]);
+    }
+}